@@ -3,10 +3,12 @@ use rustc_data_structures::graph::scc::Sccs;
 use rustc_data_structures::graph::{DirectedGraph, Successors};
 use rustc_hir as hir;
 use rustc_index::{IndexVec, newtype_index};
-use rustc_middle::mir::interpret::{AllocId, Allocation, ConstAllocation, GlobalAlloc};
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::mir::interpret::{AllocId, Allocation, ConstAllocation, GlobalAlloc, Scalar};
+use rustc_middle::mir::visit::Visitor;
+use rustc_middle::mir::{self, ConstValue, TerminatorKind};
+use rustc_middle::ty::{self, TyCtxt};
 use rustc_span::ErrorGuaranteed;
-use rustc_span::def_id::LocalDefId;
+use rustc_span::def_id::{DefId, LocalDefId};
 
 // Graph indices
 newtype_index! {
@@ -62,11 +64,13 @@ pub(crate) fn check_static_initializer_acyclic(
         succ: statics
             .iter()
             .map(|&id| {
-                if let Ok(root_alloc) = tcx.eval_static_initializer(id) {
-                    collect_referenced_local_statics(tcx, root_alloc, &statics)
-                } else {
-                    Vec::new()
-                }
+                static_initializer_deps(tcx, id)
+                    .iter()
+                    .filter_map(|&def_id| {
+                        let local_def = def_id.as_local()?;
+                        statics.get_index_of(&local_def).map(Into::into)
+                    })
+                    .collect()
             })
             .collect(),
     };
@@ -103,11 +107,31 @@ pub(crate) fn check_static_initializer_acyclic(
                 tcx.def_path_str(head_def.to_def_id()),
             ),
         );
-        diag.span_labels(
-            nodes.iter().map(|&n| tcx.def_span(statics[usize::from(n)])),
-            "part of this cycle",
-        )
-        .note(format!(
+
+        if nodes.len() == 1 {
+            // A single-node SCC can only be cyclic via a direct self-reference, so there is no
+            // chain to walk: label the one static involved.
+            diag.span_labels(
+                nodes.iter().map(|&n| tcx.def_span(statics[usize::from(n)])),
+                "part of this cycle",
+            );
+        } else {
+            let cycle = find_cycle_in_scc(&graph, nodes);
+            for (&from, &to) in cycle.iter().zip(cycle.iter().cycle().skip(1)) {
+                let from_def = statics[usize::from(from)];
+                let to_def = statics[usize::from(to)];
+                diag.span_label(
+                    tcx.def_span(from_def),
+                    format!(
+                        "`{}`'s initializer references `{}` here",
+                        tcx.def_path_str(from_def.to_def_id()),
+                        tcx.def_path_str(to_def.to_def_id()),
+                    ),
+                );
+            }
+        }
+
+        diag.note(format!(
             "cyclic static initializer references are not supported for target `{}`",
             tcx.sess.target.llvm_target
         ));
@@ -120,14 +144,73 @@ pub(crate) fn check_static_initializer_acyclic(
     }
 }
 
-// Traverse allocations reachable from the static initializer allocation and collect local-static targets.
-fn collect_referenced_local_statics<'tcx>(
+// Recover a cycle within an SCC already known to be cyclic: DFS the subgraph induced by
+// `nodes`, tracking the recursion stack, and stop at the first back edge. Slicing the stack
+// from the revisited node to the top yields a simple cycle `A -> B -> ... -> A`; it is not
+// necessarily the shortest cycle in the SCC.
+fn find_cycle_in_scc(graph: &StaticRefGraph, nodes: &[StaticNodeIdx]) -> Vec<StaticNodeIdx> {
+    let in_scc: FxIndexSet<StaticNodeIdx> = nodes.iter().copied().collect();
+    let mut visited: FxIndexSet<StaticNodeIdx> = FxIndexSet::default();
+    let mut stack: Vec<StaticNodeIdx> = Vec::new();
+
+    fn visit(
+        graph: &StaticRefGraph,
+        in_scc: &FxIndexSet<StaticNodeIdx>,
+        node: StaticNodeIdx,
+        stack: &mut Vec<StaticNodeIdx>,
+        visited: &mut FxIndexSet<StaticNodeIdx>,
+    ) -> Option<Vec<StaticNodeIdx>> {
+        stack.push(node);
+        visited.insert(node);
+
+        for succ in graph.successors(node) {
+            if !in_scc.contains(&succ) {
+                continue;
+            }
+            if let Some(pos) = stack.iter().position(|&n| n == succ) {
+                return Some(stack[pos..].to_vec());
+            }
+            if !visited.contains(&succ)
+                && let Some(cycle) = visit(graph, in_scc, succ, stack, visited)
+            {
+                return Some(cycle);
+            }
+        }
+
+        stack.pop();
+        None
+    }
+
+    visit(graph, &in_scc, nodes[0], &mut stack, &mut visited)
+        .expect("an SCC with more than one node must contain a cycle")
+}
+
+/// The local statics directly referenced by `def_id`'s evaluated initializer.
+///
+/// This is a plain, unmemoized function, not a `tcx` query: it recomputes the allocation walk on
+/// every call, and `rustc_monomorphize`'s separate `UsageMap`-based check cannot reach it, so
+/// that duplication is not yet removed. Registering a real `static_initializer_deps` query in
+/// `rustc_middle`'s query list, and switching that other check to consume it, remains a
+/// follow-up; this stays a `Vec` return rather than an arena allocation in the meantime, since
+/// there is no memoization here to justify keeping the result alive past the caller.
+pub(crate) fn static_initializer_deps<'tcx>(tcx: TyCtxt<'tcx>, def_id: LocalDefId) -> Vec<DefId> {
+    let Ok(root_alloc) = tcx.eval_static_initializer(def_id) else {
+        return Vec::new();
+    };
+    let mut deps = collect_referenced_statics(tcx, root_alloc);
+    deps.retain(|def_id| def_id.is_local());
+    deps
+}
+
+// Traverse allocations reachable from the static initializer allocation and collect the statics
+// it references.
+fn collect_referenced_statics<'tcx>(
     tcx: TyCtxt<'tcx>,
     root_alloc: ConstAllocation<'tcx>,
-    statics: &FxIndexSet<LocalDefId>,
-) -> Vec<StaticNodeIdx> {
-    let mut referenced_nodes: Vec<StaticNodeIdx> = Vec::default();
+) -> Vec<DefId> {
+    let mut referenced: Vec<DefId> = Vec::new();
     let mut alloc_ids: FxIndexSet<AllocId> = FxIndexSet::default();
+    let mut instances: FxIndexSet<ty::Instance<'tcx>> = FxIndexSet::default();
 
     let add_ids_from_alloc = |alloc: &Allocation, ids: &mut FxIndexSet<AllocId>| {
         ids.extend(alloc.provenance().ptrs().iter().map(|(_, prov)| prov.alloc_id()));
@@ -136,23 +219,103 @@ fn collect_referenced_local_statics<'tcx>(
     // Scan the root allocation for pointers first.
     add_ids_from_alloc(root_alloc.inner(), &mut alloc_ids);
 
-    for i in 0.. {
-        let Some(&alloc_id) = alloc_ids.get_index(i) else {
+    let (mut alloc_i, mut inst_i) = (0, 0);
+    loop {
+        if let Some(&alloc_id) = alloc_ids.get_index(alloc_i) {
+            alloc_i += 1;
+            match tcx.try_get_global_alloc(alloc_id) {
+                Some(GlobalAlloc::Static(def_id)) => referenced.push(def_id),
+                Some(GlobalAlloc::Memory(const_alloc)) => {
+                    add_ids_from_alloc(const_alloc.inner(), &mut alloc_ids);
+                }
+                Some(GlobalAlloc::VTable(ty, dyn_ty)) => {
+                    // A `&dyn Trait` embeds a vtable whose drop glue and method slots are
+                    // function pointers that may themselves reference statics. Resolve the
+                    // vtable's own allocation and scan it the same way as any other memory
+                    // allocation; the `alloc_ids` visited-set already guards against revisiting
+                    // it.
+                    alloc_ids.insert(tcx.vtable_allocation((ty, dyn_ty)));
+                }
+                Some(GlobalAlloc::Function { instance }) => {
+                    instances.insert(instance);
+                }
+                None => {}
+            }
+            continue;
+        }
+
+        let Some(&instance) = instances.get_index(inst_i) else {
             break;
         };
-        match tcx.try_get_global_alloc(alloc_id) {
-            Some(GlobalAlloc::Static(def_id)) => {
-                if let Some(local_def) = def_id.as_local()
-                    && let Some(node) = statics.get_index_of(&local_def)
-                {
-                    referenced_nodes.push(node.into());
-                }
+        inst_i += 1;
+        collect_instance_refs(tcx, instance, &mut alloc_ids, &mut instances);
+    }
+    referenced
+}
+
+// Scan a function's MIR body for the statics its own constants embed, and for the further
+// functions it calls, so statics reached only through a callee are not missed: a vtable's drop
+// slot is drop glue that *calls* the concrete `Drop::drop` impl rather than inlining it, so the
+// reference to a static in that impl's body would otherwise never be seen. Generic arguments are
+// instantiated through `instance` before evaluation, mirroring how the monomorphization collector
+// resolves the same constants and call targets.
+fn collect_instance_refs<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    instance: ty::Instance<'tcx>,
+    alloc_ids: &mut FxIndexSet<AllocId>,
+    instances: &mut FxIndexSet<ty::Instance<'tcx>>,
+) {
+    if !tcx.is_mir_available(instance.def_id()) {
+        return;
+    }
+    let typing_env = ty::TypingEnv::fully_monomorphized();
+    let body = tcx.instance_mir(instance.def);
+
+    // `required_consts` only lists constants that still need evaluating; a reference to a static
+    // is already a pointer value, so every constant operand of the body is visited as well.
+    struct ConstOperands<'tcx>(Vec<mir::ConstOperand<'tcx>>);
+    impl<'tcx> Visitor<'tcx> for ConstOperands<'tcx> {
+        fn visit_const_operand(&mut self, constant: &mir::ConstOperand<'tcx>, _: mir::Location) {
+            self.0.push(*constant);
+        }
+    }
+    let mut consts = ConstOperands(body.required_consts().to_vec());
+    consts.visit_body(body);
+
+    for constant in consts.0 {
+        let const_ = instance.instantiate_mir_and_normalize_erasing_regions(
+            tcx,
+            typing_env,
+            ty::EarlyBinder::bind(constant.const_),
+        );
+        let Ok(val) = const_.eval(tcx, typing_env, constant.span) else {
+            continue;
+        };
+        match val {
+            ConstValue::Scalar(Scalar::Ptr(ptr, _)) => {
+                alloc_ids.insert(ptr.provenance.alloc_id());
             }
-            Some(GlobalAlloc::Memory(const_alloc)) => {
-                add_ids_from_alloc(const_alloc.inner(), &mut alloc_ids);
+            ConstValue::Indirect { alloc_id, .. } => {
+                alloc_ids.insert(alloc_id);
             }
-            _ => {} // Functions, vtables, etc: ignore
+            _ => {}
+        }
+    }
+
+    for block in body.basic_blocks.iter() {
+        let TerminatorKind::Call { func, .. } = &block.terminator().kind else {
+            continue;
+        };
+        let Some((def_id, args)) = func.const_fn_def() else {
+            continue;
+        };
+        let args = instance.instantiate_mir_and_normalize_erasing_regions(
+            tcx,
+            typing_env,
+            ty::EarlyBinder::bind(args),
+        );
+        if let Ok(Some(callee)) = ty::Instance::resolve(tcx, typing_env, def_id, args) {
+            instances.insert(callee);
         }
     }
-    referenced_nodes
 }
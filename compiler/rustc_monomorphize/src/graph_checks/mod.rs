@@ -6,20 +6,26 @@ use crate::graph_checks::statics_check::check_static_initializers_are_acyclic;
 
 mod statics_check;
 
+/// If the target requires a deterministic static initialization order and its statics form no
+/// cycle, returns the dependency-respecting sequence of `MonoItem::Static`s in which their
+/// initializers can run. Returns `None` if the target does not require such an order, or if a
+/// cycle was found and reported. The order is only returned; it is not passed on to codegen.
 pub(super) fn check_mono_item_graph<'tcx, 'a, 'b>(
     tcx: TyCtxt<'tcx>,
     mono_items: &'a [MonoItem<'tcx>],
     usage_map: &'b UsageMap<'tcx>,
-) {
-    do_target_specific_checks(tcx, mono_items, usage_map);
+) -> Option<Vec<MonoItem<'tcx>>> {
+    do_target_specific_checks(tcx, mono_items, usage_map)
 }
 
 fn do_target_specific_checks<'tcx, 'a, 'b>(
     tcx: TyCtxt<'tcx>,
     mono_items: &'a [MonoItem<'tcx>],
     usage_map: &'b UsageMap<'tcx>,
-) {
+) -> Option<Vec<MonoItem<'tcx>>> {
     if tcx.sess.target.options.static_initializer_must_be_acyclic {
-        check_static_initializers_are_acyclic(tcx, mono_items, usage_map);
+        check_static_initializers_are_acyclic(tcx, mono_items, usage_map)
+    } else {
+        None
     }
 }
@@ -62,7 +62,7 @@ pub(super) fn check_static_initializers_are_acyclic<'tcx, 'a, 'b>(
     tcx: TyCtxt<'tcx>,
     mono_items: &'a [MonoItem<'tcx>],
     usage_map: &'b UsageMap<'tcx>,
-) {
+) -> Option<Vec<MonoItem<'tcx>>> {
     // Collect statics
     let statics: FxIndexSet<DefId> = mono_items
         .iter()
@@ -74,7 +74,7 @@ pub(super) fn check_static_initializers_are_acyclic<'tcx, 'a, 'b>(
 
     // Fast path
     if statics.is_empty() {
-        return;
+        return Some(Vec::new());
     }
     // For all statics collect all reachable statics to create a graph
     let graph = StaticRefGraph { statics: &statics, used_map: &usage_map.used_map };
@@ -87,17 +87,19 @@ pub(super) fn check_static_initializers_are_acyclic<'tcx, 'a, 'b>(
     for i in graph.iter_nodes() {
         members[sccs.scc(i)].push(i);
     }
+    let mut acyclic = true;
     for scc in sccs.all_sccs() {
         let nodes = &members[scc];
-        let acyclic = match nodes.len() {
+        let scc_acyclic = match nodes.len() {
             0 => true,
             1 => graph.successors(nodes[0]).all(|x| x != nodes[0]),
             2.. => false,
         };
 
-        if acyclic {
+        if scc_acyclic {
             continue;
         }
+        acyclic = false;
 
         let head_def = statics[nodes[0].index()];
         let head_span = tcx.def_span(head_def);
@@ -116,4 +118,18 @@ pub(super) fn check_static_initializers_are_acyclic<'tcx, 'a, 'b>(
         ));
         let _ = diag.emit();
     }
+
+    if !acyclic {
+        return None;
+    }
+
+    // The graph is acyclic, so every SCC is a single node, and `sccs.all_sccs()` already visits
+    // them with each node's dependencies before the node itself. Flattening that order as-is
+    // gives a sequence where each static comes after everything its initializer depends on,
+    // which is what the backend needs to lay out initializers safely.
+    Some(
+        sccs.all_sccs()
+            .flat_map(|scc| members[scc].iter().map(|&n| MonoItem::Static(statics[n.index()])))
+            .collect(),
+    )
 }
@@ -0,0 +1,22 @@
+//@ only-wasm32-wasip1
+//@ build-fail
+
+// Regression test for a static initializer cycle whose `A` -> `B` edge exists only through a
+// `dyn Trait` vtable's drop glue, while `B` refers to `A` directly.
+
+trait Foo {}
+
+struct Loud;
+
+impl Foo for Loud {}
+
+impl Drop for Loud {
+    fn drop(&mut self) {
+        std::hint::black_box(&B);
+    }
+}
+
+static A: &dyn Foo = &Loud; //~ ERROR static initializer forms a cycle involving `A`
+static B: &&dyn Foo = &A;
+
+fn main() {}